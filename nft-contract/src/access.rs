@@ -0,0 +1,78 @@
+use crate::*;
+use near_sdk::require;
+
+#[near_bindgen]
+impl Contract {
+    /// Grant `account_id` the "medic" role, letting it call role-gated admin
+    /// methods (e.g. `vaxxx`) alongside the owner. Owner-only.
+    pub fn add_medic(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.medics.insert(&account_id);
+    }
+
+    /// Revoke `account_id`'s medic role. Owner-only.
+    pub fn remove_medic(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.medics.remove(&account_id);
+    }
+
+    pub fn is_medic(&self, account_id: AccountId) -> bool {
+        self.medics.contains(&account_id)
+    }
+
+    /// Freeze minting and transfers. Owner-only.
+    pub fn pause(&mut self) {
+        self.assert_owner();
+        self.paused = true;
+    }
+
+    /// Resume minting and transfers. Owner-only.
+    pub fn unpause(&mut self) {
+        self.assert_owner();
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Step one of a two-step ownership transfer: the current owner nominates
+    /// a successor, who must call `accept_owner` to complete the handoff.
+    /// This avoids bricking the contract by mistyping a new owner's account id.
+    pub fn propose_owner(&mut self, new_owner_id: AccountId) {
+        self.assert_owner();
+        self.pending_owner_id = Some(new_owner_id);
+    }
+
+    /// Step two: the nominated successor accepts ownership.
+    pub fn accept_owner(&mut self) {
+        let pending_owner_id = self.pending_owner_id.take().expect("No pending owner");
+        require!(
+            env::predecessor_account_id() == pending_owner_id,
+            "Only the proposed owner can accept ownership"
+        );
+        self.owner_id = pending_owner_id;
+    }
+
+    /// Panics unless the predecessor is the owner.
+    pub(crate) fn assert_owner(&self) {
+        require!(
+            env::predecessor_account_id() == self.owner_id,
+            "Owner-only method"
+        );
+    }
+
+    /// Panics unless the predecessor is the owner or a medic.
+    pub(crate) fn require_role(&self) {
+        let predecessor = env::predecessor_account_id();
+        require!(
+            predecessor == self.owner_id || self.medics.contains(&predecessor),
+            "Requires owner or medic role"
+        );
+    }
+
+    /// Panics if the contract is currently paused.
+    pub(crate) fn assert_not_paused(&self) {
+        require!(!self.paused, "Contract is paused");
+    }
+}
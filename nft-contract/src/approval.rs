@@ -0,0 +1,188 @@
+use crate::*;
+use near_sdk::{ext_contract, require, Balance, Gas};
+
+pub const GAS_FOR_NFT_APPROVE: Gas = Gas(10_000_000_000_000);
+pub const NO_DEPOSIT: Balance = 0;
+
+/// When a granted approval self-revokes. Mirrors SNIP-721-style permits so a
+/// marketplace can be handed a transfer window that expires on its own
+/// instead of relying on the owner remembering to `nft_revoke` it.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Expiration {
+    AtHeight(u64),
+    AtTime(u64),
+    Never,
+}
+
+impl Expiration {
+    pub fn has_expired(&self) -> bool {
+        match self {
+            Expiration::AtHeight(height) => env::block_height() >= *height,
+            Expiration::AtTime(timestamp) => env::block_timestamp() >= *timestamp,
+            Expiration::Never => false,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Approval {
+    pub approval_id: u64,
+    pub expires_at: Expiration,
+}
+
+impl Token {
+    /// Drop any approvals whose expiration has already passed, reclaiming
+    /// their storage. Called by `nft_approve` and by `internal_transfer`
+    /// before it checks approvals; `nft_revoke` does not prune.
+    pub(crate) fn prune_expired_approvals(&mut self) {
+        self.approved_account_ids
+            .retain(|_, approval| !approval.expires_at.has_expired());
+    }
+
+    /// The account's approval, if it exists and hasn't expired.
+    pub(crate) fn active_approval(&self, account_id: &AccountId) -> Option<&Approval> {
+        self.approved_account_ids
+            .get(account_id)
+            .filter(|approval| !approval.expires_at.has_expired())
+    }
+}
+
+pub trait NonFungibleTokenCore {
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expiration: Option<Expiration>,
+        msg: Option<String>,
+    ) -> Option<Promise>;
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool;
+
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId);
+
+    fn nft_revoke_all(&mut self, token_id: TokenId);
+}
+
+#[ext_contract(ext_non_fungible_approval_receiver)]
+trait NonFungibleTokenApprovalReceiver {
+    fn nft_on_approve(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        approval_id: u64,
+        msg: String,
+    );
+}
+
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expiration: Option<Expiration>,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        let mut token = self.tokens_by_id.get(&token_id).expect("No token found");
+
+        require!(
+            env::predecessor_account_id() == token.owner_id,
+            "Predecessor must be the token owner"
+        );
+
+        token.prune_expired_approvals();
+
+        let initial_storage_usage = env::storage_usage();
+
+        let approval_id = token.next_approval_id;
+        token.approved_account_ids.insert(
+            account_id.clone(),
+            Approval {
+                approval_id,
+                expires_at: expiration.unwrap_or(Expiration::Never),
+            },
+        );
+
+        token.next_approval_id += 1;
+        self.tokens_by_id.insert(&token_id, &token);
+
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        msg.map(|msg| {
+            ext_non_fungible_approval_receiver::nft_on_approve(
+                token_id,
+                token.owner_id,
+                approval_id,
+                msg,
+                account_id,
+                NO_DEPOSIT,
+                env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
+            )
+        })
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        let token = self.tokens_by_id.get(&token_id).expect("No token found");
+
+        let approval = match token.active_approval(&approved_account_id) {
+            Some(approval) => approval,
+            None => return false,
+        };
+
+        match approval_id {
+            Some(approval_id) => approval_id == approval.approval_id,
+            None => true,
+        }
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+        assert_one_yocto();
+        let mut token = self.tokens_by_id.get(&token_id).expect("No token found");
+
+        require!(
+            env::predecessor_account_id() == token.owner_id,
+            "Predecessor must be the token owner"
+        );
+
+        if token.approved_account_ids.remove(&account_id).is_some() {
+            self.tokens_by_id.insert(&token_id, &token);
+        }
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let mut token = self.tokens_by_id.get(&token_id).expect("No token found");
+
+        require!(
+            env::predecessor_account_id() == token.owner_id,
+            "Predecessor must be the token owner"
+        );
+
+        if !token.approved_account_ids.is_empty() {
+            token.approved_account_ids.clear();
+            self.tokens_by_id.insert(&token_id, &token);
+        }
+    }
+}
+
+pub(crate) fn assert_one_yocto() {
+    require!(
+        env::attached_deposit() == 1,
+        "Requires attached deposit of exactly 1 yoctoNEAR"
+    );
+}
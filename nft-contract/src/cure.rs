@@ -1,15 +1,30 @@
 use crate::*;
 
+/// Curing every infected token in one call can blow the gas budget for an
+/// account holding hundreds of tokens, so a no-argument `nft_cure` only
+/// clears this many per call instead of "all".
+const DEFAULT_CURE_BATCH: u64 = 20;
+
 #[near_bindgen]
 impl Contract {
+    /// Cures up to `limit` tokens (or `DEFAULT_CURE_BATCH` if omitted) owned
+    /// by the caller, burning them to `burn.near`. Returns `(cured, remaining)`
+    /// so a heavily infected account can tell how many more calls it needs.
     #[payable]
-    pub fn nft_cure(&mut self) {
+    pub fn nft_cure(&mut self, limit: Option<u64>) -> (u64, u64) {
         // get the sender address
         let sender_id = env::predecessor_account_id();
         let burn_address: AccountId = AccountId::new_unchecked("burn.near".to_string());
+        let batch_size = limit.unwrap_or(DEFAULT_CURE_BATCH) as usize;
 
-        // get a token to cure
-        let tokens = self.tokens_per_owner.get(&sender_id).expect("Account not infected.").to_vec();
+        // get a bounded slice of tokens to cure
+        let tokens: Vec<TokenId> = self
+            .tokens_per_owner
+            .get(&sender_id)
+            .expect("Account not infected.")
+            .iter()
+            .take(batch_size)
+            .collect();
 
         tokens.iter()
             .map(|token_id| self.internal_transfer(
@@ -20,8 +35,17 @@ impl Contract {
                 None))
             .for_each(drop);
 
-        //console log confirming that the account has been cured
-        env::log_str("Cured of thevarus");
-    }
+        ThevarusCureLog {
+            account_id: sender_id.clone(),
+            burned_token_ids: tokens.iter().map(|token_id| token_id.to_string()).collect(),
+        }
+        .emit();
 
-}
\ No newline at end of file
+        let remaining = self
+            .tokens_per_owner
+            .get(&sender_id)
+            .map_or(0, |remaining_tokens| remaining_tokens.len());
+
+        (tokens.len() as u64, remaining)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    pub fn nft_total_supply(&self) -> U128 {
+        U128(self.token_metadata_by_id.len() as u128)
+    }
+
+    pub fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonToken> {
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.token_metadata_by_id
+            .keys()
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .map(|token_id| self.json_token(token_id))
+            .collect()
+    }
+
+    pub fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
+        if let Some(tokens_for_owner_set) = tokens_for_owner_set {
+            U128(tokens_for_owner_set.len() as u128)
+        } else {
+            U128(0)
+        }
+    }
+
+    pub fn nft_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        let tokens_for_owner_set = self.tokens_per_owner.get(&account_id);
+        let tokens = if let Some(tokens_for_owner_set) = tokens_for_owner_set {
+            tokens_for_owner_set
+        } else {
+            return vec![];
+        };
+
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        tokens
+            .iter()
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .map(|token_id| self.json_token(token_id))
+            .collect()
+    }
+
+    pub(crate) fn json_token(&self, token_id: TokenId) -> JsonToken {
+        let token = self.tokens_by_id.get(&token_id).expect("No token found");
+        let metadata = self.token_metadata_by_id.get(&token_id).expect("No metadata found");
+
+        let approved_account_ids = token
+            .approved_account_ids
+            .iter()
+            .filter(|(_, approval)| !approval.expires_at.has_expired())
+            .map(|(account_id, approval)| (account_id.clone(), approval.approval_id))
+            .collect();
+
+        JsonToken {
+            token_id,
+            owner_id: token.owner_id,
+            metadata,
+            approved_account_ids,
+            royalty: token.royalty,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::*;
+use near_sdk::serde::Serialize;
+use near_sdk::serde_json::json;
+
+/// Mirrors what a `#[derive(Nep297)]` macro would generate: an implementor
+/// only declares its event name (and, rarely, a non-default standard/version),
+/// and gets a NEP-297-compliant `emit()` for free -- logged as
+/// `EVENT_JSON:{"standard":...,"version":...,"event":...,"data":[...]}`.
+pub trait Nep297: Serialize + Sized {
+    fn standard() -> &'static str {
+        NFT_STANDARD_NAME
+    }
+
+    fn version() -> &'static str {
+        NFT_METADATA_SPEC
+    }
+
+    fn event() -> &'static str;
+
+    fn emit(&self) {
+        env::log_str(&format!(
+            "EVENT_JSON:{}",
+            json!({
+                "standard": Self::standard(),
+                "version": Self::version(),
+                "event": Self::event(),
+                "data": [self],
+            })
+        ));
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftMintLog {
+    pub owner_id: String,
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl Nep297 for NftMintLog {
+    fn event() -> &'static str {
+        "nft_mint"
+    }
+}
+
+impl NftMintLog {
+    /// Emit one `nft_mint` event covering every entry in `logs`, so a batch
+    /// mint across several receivers still produces a single event instead
+    /// of one per token.
+    pub fn emit_many(logs: Vec<NftMintLog>) {
+        env::log_str(&format!(
+            "EVENT_JSON:{}",
+            json!({
+                "standard": NftMintLog::standard(),
+                "version": NftMintLog::version(),
+                "event": NftMintLog::event(),
+                "data": logs,
+            })
+        ));
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NftTransferLog {
+    pub authorized_id: Option<String>,
+    pub old_owner_id: String,
+    pub new_owner_id: String,
+    pub token_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl Nep297 for NftTransferLog {
+    fn event() -> &'static str {
+        "nft_transfer"
+    }
+}
+
+/// Emitted whenever `internal_spawn_mutant` forges a new infected token,
+/// carrying both the original and mutant token ids so indexers can follow a
+/// single infection as it branches.
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ThevarusMutationLog {
+    pub original_token_id: String,
+    pub mutant_token_id: String,
+    pub previous_owner_id: AccountId,
+    pub mutant_owner_id: AccountId,
+}
+
+impl Nep297 for ThevarusMutationLog {
+    fn standard() -> &'static str {
+        THEVARUS_STANDARD_NAME
+    }
+
+    fn event() -> &'static str {
+        "thevarus_mutation"
+    }
+}
+
+/// Emitted by `nft_cure` with every token id it burned in that call.
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ThevarusCureLog {
+    pub account_id: AccountId,
+    pub burned_token_ids: Vec<String>,
+}
+
+impl Nep297 for ThevarusCureLog {
+    fn standard() -> &'static str {
+        THEVARUS_STANDARD_NAME
+    }
+
+    fn event() -> &'static str {
+        "thevarus_cure"
+    }
+}
+
+/// Emitted by `vaxxx` for the newly vaccinated account.
+#[derive(Serialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct VaccinationLog {
+    pub account_id: AccountId,
+}
+
+impl Nep297 for VaccinationLog {
+    fn standard() -> &'static str {
+        THEVARUS_STANDARD_NAME
+    }
+
+    fn event() -> &'static str {
+        "vaccination"
+    }
+}
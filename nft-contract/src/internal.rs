@@ -0,0 +1,160 @@
+use crate::*;
+use near_sdk::{require, Balance, CryptoHash, Promise};
+
+pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
+    hash
+}
+
+/// Refund the predecessor for any attached deposit that wasn't consumed by storage.
+pub(crate) fn refund_deposit(storage_used: u64) {
+    let required_cost = env::storage_byte_cost() * Balance::from(storage_used);
+    let attached_deposit = env::attached_deposit();
+
+    require!(
+        required_cost <= attached_deposit,
+        format!("Must attach {} yoctoNEAR to cover storage", required_cost)
+    );
+
+    let refund = attached_deposit - required_cost;
+    if refund > 1 {
+        Promise::new(env::predecessor_account_id()).transfer(refund);
+    }
+}
+
+impl Contract {
+    //add a token to the set of tokens an owner has
+    pub(crate) fn internal_add_token_to_owner(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        let mut tokens_set = self.tokens_per_owner.get(account_id).unwrap_or_else(|| {
+            UnorderedSet::new(
+                StorageKey::TokenPerOwnerInner {
+                    account_id_hash: hash_account_id(account_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        tokens_set.insert(token_id);
+        self.tokens_per_owner.insert(account_id, &tokens_set);
+    }
+
+    //remove a token from an owner's set of tokens
+    pub(crate) fn internal_remove_token_from_owner(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        let mut tokens_set = self
+            .tokens_per_owner
+            .get(account_id)
+            .expect("Token should be owned by the sender");
+
+        tokens_set.remove(token_id);
+
+        if tokens_set.is_empty() {
+            self.tokens_per_owner.remove(account_id);
+        } else {
+            self.tokens_per_owner.insert(account_id, &tokens_set);
+        }
+    }
+
+    /// Move `token_id` from `sender_id` to `receiver_id`, enforcing ownership and approvals.
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) -> Token {
+        let mut token = self.tokens_by_id.get(token_id).expect("No token found");
+        token.prune_expired_approvals();
+
+        if sender_id != &token.owner_id {
+            let approval = token
+                .active_approval(sender_id)
+                .expect("Unauthorized");
+
+            if let Some(enforced_approval_id) = approval_id {
+                require!(
+                    approval.approval_id == enforced_approval_id,
+                    format!(
+                        "The actual approval_id {} is different from the given approval_id {}",
+                        approval.approval_id, enforced_approval_id
+                    )
+                );
+            }
+        }
+
+        require!(
+            &token.owner_id != receiver_id,
+            "The token owner and the receiver should be different"
+        );
+
+        self.internal_remove_token_from_owner(&token.owner_id, token_id);
+        self.internal_add_token_to_owner(receiver_id, token_id);
+
+        let new_token = Token {
+            owner_id: receiver_id.clone(),
+            approved_account_ids: Default::default(),
+            next_approval_id: token.next_approval_id,
+            royalty: token.royalty.clone(),
+        };
+        self.tokens_by_id.insert(token_id, &new_token);
+
+        if let Some(memo) = memo.as_ref() {
+            env::log_str(&format!("Memo: {}", memo).to_string());
+        }
+
+        token
+    }
+
+    /// thevarus never really leaves: every `nft_transfer` and
+    /// `nft_transfer_call` forges a fresh "mutant" token cloned from
+    /// `original_token_id`'s metadata and hands it to `mutant_receiver_id`, so
+    /// moving the original never shrinks the total infection count on its own.
+    /// Transfers made by `nft_cure`, `nft_transfer_payout` and the revert in
+    /// `nft_resolve_transfer` go through `internal_transfer` only and spawn no
+    /// mutant.
+    pub(crate) fn internal_spawn_mutant(
+        &mut self,
+        previous_owner_id: &AccountId,
+        mutant_receiver_id: &AccountId,
+        original_token_id: &TokenId,
+    ) -> TokenId {
+        let metadata = self
+            .token_metadata_by_id
+            .get(original_token_id)
+            .expect("No metadata found");
+
+        let mutant_token_id = self.next_token_id;
+        self.next_token_id += 1;
+
+        let mutant_token = Token {
+            owner_id: mutant_receiver_id.clone(),
+            approved_account_ids: Default::default(),
+            next_approval_id: 0,
+            royalty: Default::default(),
+        };
+
+        self.tokens_by_id.insert(&mutant_token_id, &mutant_token);
+        self.token_metadata_by_id.insert(&mutant_token_id, &metadata);
+        self.internal_add_token_to_owner(mutant_receiver_id, &mutant_token_id);
+
+        ThevarusMutationLog {
+            original_token_id: original_token_id.to_string(),
+            mutant_token_id: mutant_token_id.to_string(),
+            previous_owner_id: previous_owner_id.clone(),
+            mutant_owner_id: mutant_receiver_id.clone(),
+        }
+        .emit();
+
+        mutant_token_id
+    }
+}
@@ -16,7 +16,8 @@ pub use crate::royalty::*;
 pub use crate::events::*;
 
 mod internal;
-mod approval; 
+mod access;
+mod approval;
 mod enumeration; 
 mod metadata; 
 mod mint; 
@@ -24,11 +25,15 @@ mod nft_core;
 mod royalty; 
 mod events;
 mod cure;
+mod upgrade;
 
 /// This spec can be treated like a version of the standard.
 pub const NFT_METADATA_SPEC: &str = "nft-1.0.0";
 /// This is the name of the NFT standard we're using
 pub const NFT_STANDARD_NAME: &str = "nep171";
+/// Standard tag for this contract's own events (mutation/cure/vaccination) --
+/// these aren't part of NEP-171 and must not be logged under its name.
+pub const THEVARUS_STANDARD_NAME: &str = "thevarus";
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
@@ -53,6 +58,15 @@ pub struct Contract {
 
     //index number for tokens
     pub next_token_id: TokenId,
+
+    //accounts allowed to act as medics (role-gated admin calls) alongside the owner
+    pub medics: UnorderedSet<AccountId>,
+
+    //freezes nft_mint/nft_transfer when true
+    pub paused: bool,
+
+    //owner nominated via propose_owner, awaiting accept_owner
+    pub pending_owner_id: Option<AccountId>,
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -68,6 +82,7 @@ pub enum StorageKey {
     TokenTypesLocked,
     Vaxxxed,
     NextTokenId,
+    Medics,
 }
 
 #[near_bindgen]
@@ -116,12 +131,51 @@ impl Contract {
                 Some(&metadata),
             ),
             vaxxxed: UnorderedSet::new(StorageKey::Vaxxxed.try_to_vec().unwrap(),),
-            next_token_id: 0
+            next_token_id: 0,
+            medics: UnorderedSet::new(StorageKey::Medics.try_to_vec().unwrap()),
+            paused: false,
+            pending_owner_id: None,
         };
 
         //return the Contract object
         this
     }
+
+    /// Add an account to the vaxxxed set. Idempotent: vaxxxing twice is a no-op.
+    /// Restricted to the owner or a medic.
+    pub fn vaxxx(&mut self, account_id: AccountId) {
+        self.require_role();
+        if self.vaxxxed.insert(&account_id) {
+            VaccinationLog { account_id }.emit();
+        }
+    }
+
+    /// Whether `account_id` currently holds a vaxxx pass.
+    pub fn vaxxx_pass(&self, account_id: AccountId) -> bool {
+        self.vaxxxed.contains(&account_id)
+    }
+
+    /// Every vaxxxed account, in insertion order.
+    pub fn vaxxx_list(&self) -> Vec<AccountId> {
+        self.vaxxxed.to_vec()
+    }
+
+    /// Vaccinate a whole cohort in one call. Restricted to the owner or a
+    /// medic. Refunds any storage deposit left over once every account has
+    /// been recorded.
+    #[payable]
+    pub fn vaxxx_batch(&mut self, accounts: Vec<AccountId>) {
+        self.require_role();
+
+        let initial_storage_usage = env::storage_usage();
+
+        for account_id in accounts {
+            self.vaxxxed.insert(&account_id);
+            VaccinationLog { account_id }.emit();
+        }
+
+        refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
 }
 
 #[cfg(test)]
@@ -271,6 +325,42 @@ mod tests {
         assert_eq!(expected.reference_hash, actual.reference_hash, "Expected reference_hash to be equal.");
     }
 
+    /// Ensure nft_batch_mint allocates sequential token ids across receivers
+    #[test]
+    fn batch_mint_allocates_sequential_ids() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_batch_mint(vec![
+            (alice(), get_thevarus()),
+            (alice(), get_thevarus()),
+            (bob(), get_thevarus()),
+        ]);
+
+        assert_eq!(3, contract.nft_total_supply().0, "Expected three tokens to be minted.");
+        assert_eq!(2, contract.nft_supply_for_owner(alice()).0, "Expected alice to own two tokens.");
+        assert_eq!(1, contract.nft_supply_for_owner(bob()).0, "Expected bob to own one token.");
+
+        let alice_tokens = contract.nft_tokens_for_owner(alice(), None, None);
+        assert_eq!(0u64, alice_tokens[0].token_id, "Expected first minted token id to be 0.");
+        assert_eq!(1u64, alice_tokens[1].token_id, "Expected second minted token id to be 1.");
+
+        let bob_tokens = contract.nft_tokens_for_owner(bob(), None, None);
+        assert_eq!(2u64, bob_tokens[0].token_id, "Expected third minted token id to be 2.");
+    }
+
+    /// Ensure nft_batch_mint rejects callers that are neither owner nor medic
+    #[test]
+    #[should_panic(expected = "Requires owner or medic role")]
+    fn batch_mint_rejects_non_medic() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_batch_mint(vec![(alice(), get_thevarus())]);
+    }
+
     ////////////////////
     //// Cure Tests ////
     ////////////////////
@@ -298,7 +388,7 @@ mod tests {
         assert_eq!(U128::from(1), contract.nft_supply_for_owner(alice()), "Alice should be infected.");
 
         // Cure self
-        contract.nft_cure();
+        contract.nft_cure(None);
 
         let token = contract.tokens_by_id.get(&original()).unwrap();
         assert_eq!(burn(), token.owner_id, "Token should belong to burn after transfer.");
@@ -336,7 +426,7 @@ mod tests {
         assert_eq!(U128::from(2), contract.nft_supply_for_owner(alice()), "Alice should be infected.");
 
         // Cure self
-        contract.nft_cure();
+        contract.nft_cure(None);
 
         // Get the minted token
         let cured1 = contract.tokens_by_id.get(&original()).unwrap();
@@ -356,7 +446,46 @@ mod tests {
         let mut contract = Contract::new_default_meta(contract());
 
         // Cure self
-        contract.nft_cure();
+        contract.nft_cure(None);
+    }
+
+    /// Mint well past a single bounded `nft_cure` batch and confirm repeated
+    /// calls eventually drive `nft_supply_for_owner` to zero without ever
+    /// trying to cure everything in one shot.
+    #[test]
+    fn check_bounded_cure_drains_in_batches() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        let infection_count = 45;
+        for _ in 0..infection_count {
+            contract.nft_mint(get_thevarus(), alice(), None);
+        }
+        assert_eq!(
+            U128::from(infection_count as u128),
+            contract.nft_supply_for_owner(alice()),
+            "Alice should be infected with every minted token."
+        );
+
+        let limit = 20;
+        let mut total_cured = 0u64;
+        loop {
+            let (cured, remaining) = contract.nft_cure(Some(limit));
+            assert!(cured <= limit, "A bounded cure must not exceed its limit.");
+            total_cured += cured;
+
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        assert_eq!(infection_count as u64, total_cured, "Every infected token should eventually be cured.");
+        assert_eq!(
+            U128::from(0),
+            contract.nft_supply_for_owner(alice()),
+            "Alice should no longer be infected after enough bounded calls."
+        );
     }
 
     ////////////////////////
@@ -413,6 +542,278 @@ mod tests {
         assert_eq!(carol(), token.owner_id, "Token should belong to bob after transfer.");
     }
 
+    /////////////////////////////
+    //// Resolve Transfer Tests ////
+    /////////////////////////////
+
+    /// Drive `nft_resolve_transfer` directly with a rejecting promise result,
+    /// using the "conventional" mutant routing (`mutant_receiver_id ==
+    /// owner_id`) the doc comment describes. This must revert the original
+    /// token back to the owner *and* must not panic trying to "revert" a
+    /// mutant that's already sitting with the owner.
+    #[test]
+    fn resolve_transfer_reverts_on_rejection_with_conventional_mutant() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+
+        // Mimic what `nft_transfer_call` does before it fires off the
+        // cross-contract call: move the original to bob, and spawn its
+        // mutant back to alice (the conventional routing).
+        let previous_token =
+            contract.internal_transfer(&alice(), &bob(), &original(), None, None);
+        let mutant_token_id =
+            contract.internal_spawn_mutant(&previous_token.owner_id, &alice(), &original());
+
+        testing_env!(
+            get_context(contract().to_string(), 0),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            HashMap::default(),
+            vec![near_sdk::PromiseResult::Successful(
+                near_sdk::serde_json::to_vec(&true).unwrap()
+            )]
+        );
+
+        let transferred = contract.nft_resolve_transfer(
+            alice(),
+            bob(),
+            alice(),
+            original(),
+            mutant_token_id,
+            previous_token.approved_account_ids,
+        );
+
+        assert!(!transferred, "A rejected transfer counts as reverted, not completed.");
+        assert_eq!(
+            alice(),
+            contract.tokens_by_id.get(&original()).unwrap().owner_id,
+            "The original token should be back with alice after a rejected transfer."
+        );
+        assert_eq!(
+            alice(),
+            contract.tokens_by_id.get(&mutant_token_id).unwrap().owner_id,
+            "The mutant should still belong to alice -- it was never moved in the first place."
+        );
+    }
+
+    /// A failed promise (the receiver's `nft_on_transfer` panicked, or the
+    /// receipt itself failed) must revert exactly like an explicit rejection.
+    #[test]
+    fn resolve_transfer_reverts_on_failed_promise() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+
+        let previous_token =
+            contract.internal_transfer(&alice(), &bob(), &original(), None, None);
+        let mutant_token_id =
+            contract.internal_spawn_mutant(&previous_token.owner_id, &carol(), &original());
+
+        testing_env!(
+            get_context(contract().to_string(), 0),
+            near_sdk::VMConfig::test(),
+            near_sdk::RuntimeFeesConfig::test(),
+            HashMap::default(),
+            vec![near_sdk::PromiseResult::Failed]
+        );
+
+        let transferred = contract.nft_resolve_transfer(
+            alice(),
+            bob(),
+            carol(),
+            original(),
+            mutant_token_id,
+            previous_token.approved_account_ids,
+        );
+
+        assert!(!transferred, "A failed promise counts as reverted, not completed.");
+        assert_eq!(
+            alice(),
+            contract.tokens_by_id.get(&original()).unwrap().owner_id,
+            "The original token should be back with alice after a failed call."
+        );
+        assert_eq!(
+            alice(),
+            contract.tokens_by_id.get(&mutant_token_id).unwrap().owner_id,
+            "The mutant should be reassigned back to alice since carol only held it transiently."
+        );
+    }
+
+    ///////////////////////
+    //// Approval Tests ////
+    ///////////////////////
+
+    /// An approval is active until its expiration height passes, at which
+    /// point it must be treated as if it were never granted.
+    #[test]
+    fn approval_expires_at_height() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+        contract.nft_approve(original(), bob(), Some(Expiration::AtHeight(10)), None);
+
+        assert!(
+            contract.nft_is_approved(original(), bob(), None),
+            "Approval should be active before its expiration height."
+        );
+
+        let mut later_context = get_context(alice().to_string(), 0);
+        later_context.block_index = 20;
+        testing_env!(later_context);
+
+        assert!(
+            !contract.nft_is_approved(original(), bob(), None),
+            "Approval should be treated as absent once its expiration height has passed."
+        );
+    }
+
+    /// An expired approval can no longer authorize a transfer on the owner's behalf.
+    #[test]
+    #[should_panic]
+    fn expired_approval_cannot_transfer() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+        contract.nft_approve(original(), bob(), Some(Expiration::AtHeight(10)), None);
+
+        let mut later_context = get_context(bob().to_string(), 0);
+        later_context.block_index = 20;
+        testing_env!(later_context);
+
+        contract.nft_transfer(bob(), bob(), original(), None, None);
+    }
+
+    ///////////////////////////////
+    //// Access Control Tests ////
+    ///////////////////////////////
+
+    /// A paused contract must refuse `nft_mint`, `nft_transfer`, and
+    /// `nft_transfer_call` alike, and resume accepting them once unpaused.
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn pause_blocks_mint() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.pause();
+        contract.nft_mint(get_thevarus(), alice(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn pause_blocks_transfer() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+        contract.pause();
+
+        testing_env!(get_context(alice().to_string(), 0));
+        contract.nft_transfer(bob(), carol(), original(), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn pause_blocks_transfer_call() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.nft_mint(get_thevarus(), alice(), None);
+        contract.pause();
+
+        testing_env!(get_context(alice().to_string(), 0));
+        contract.nft_transfer_call(bob(), carol(), original(), None, None, "".to_string());
+    }
+
+    #[test]
+    fn unpause_allows_mint_again() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.pause();
+        contract.unpause();
+        contract.nft_mint(get_thevarus(), alice(), None);
+
+        assert_eq!(alice(), contract.tokens_by_id.get(&original()).unwrap().owner_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner-only method")]
+    fn pause_rejects_non_owner() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.pause();
+    }
+
+    /// A medic can call role-gated methods like `vaxxx` alongside the owner,
+    /// and loses that ability once the role is revoked.
+    #[test]
+    #[should_panic(expected = "Requires owner or medic role")]
+    fn add_medic_grants_role_remove_medic_revokes_it() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.add_medic(alice());
+        assert!(contract.is_medic(alice()), "Expected alice to hold the medic role.");
+
+        testing_env!(get_context(alice().to_string(), 0));
+        contract.vaxxx(bob());
+        assert!(contract.vaxxx_pass(bob()), "Expected the medic's vaxxx call to succeed.");
+
+        testing_env!(get_context(contract().to_string(), 0));
+        contract.remove_medic(alice());
+        assert!(!contract.is_medic(alice()), "Expected alice's medic role to be revoked.");
+
+        testing_env!(get_context(alice().to_string(), 0));
+        contract.vaxxx(carol());
+    }
+
+    /// Ownership transfers in two steps: proposing a successor doesn't hand
+    /// over control by itself, and only the proposed account can accept it.
+    #[test]
+    fn propose_owner_then_accept_owner_transfers_ownership() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.propose_owner(bob());
+        assert_eq!(contract(), contract.owner_id, "Proposing alone must not transfer ownership yet.");
+
+        testing_env!(get_context(bob().to_string(), 0));
+        contract.accept_owner();
+
+        assert_eq!(bob(), contract.owner_id, "bob should be the owner after accepting.");
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the proposed owner can accept ownership")]
+    fn accept_owner_rejects_non_nominated_account() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.propose_owner(bob());
+
+        testing_env!(get_context(carol().to_string(), 0));
+        contract.accept_owner();
+    }
+
     /////////////////////
     //// Vaxxx Tests ////
     /////////////////////
@@ -420,7 +821,7 @@ mod tests {
     /// Check that vaxxx function adds to the vaxxxed list
     #[test]
     fn vaxxx_adds_to_vaxxxed() {
-        let context = get_context(bob().to_string(), 0);
+        let context = get_context(contract().to_string(), 0);
         testing_env!(context);
         let mut contract = Contract::new_default_meta(contract());
         assert_eq!(0, contract.vaxxxed.len(), "Expected empty vaxxx list."); // Sanity check
@@ -438,7 +839,7 @@ mod tests {
     /// Check that vaxxx_pass returns true for vaxxxed addresses and false for un-vaxxxed
     #[test]
     fn check_vaxxx_pass() {
-        let context = get_context(bob().to_string(), 0);
+        let context = get_context(contract().to_string(), 0);
         testing_env!(context);
         let mut contract = Contract::new_default_meta(contract());
         assert_eq!(0, contract.vaxxxed.len(), "Expected empty vaxxx list."); // Sanity check
@@ -451,7 +852,7 @@ mod tests {
     /// Check that the vaxxx_list contains all of the added addresses
     #[test]
     fn check_vaxxx_list() {
-        let context = get_context(bob().to_string(), 0);
+        let context = get_context(contract().to_string(), 0);
         testing_env!(context);
         let mut contract = Contract::new_default_meta(contract());
         assert_eq!(0, contract.vaxxxed.len(), "Expected empty vaxxx list."); // Sanity check
@@ -466,5 +867,31 @@ mod tests {
         assert_eq!("bob.near", vaxxxed_vector.get(1).unwrap().to_string(), "");
     }
 
+    /// Check that vaxxx_batch adds every listed account in a single call
+    #[test]
+    fn vaxxx_batch_adds_every_account() {
+        let context = get_context(contract().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+        assert_eq!(0, contract.vaxxxed.len(), "Expected empty vaxxx list."); // Sanity check
+
+        contract.vaxxx_batch(vec![alice(), bob(), carol()]);
+
+        assert_eq!(3, contract.vaxxxed.len(), "Expected all three accounts to be vaxxxed.");
+        assert!(contract.vaxxx_pass(alice()), "Expected alice to be vaxxxed");
+        assert!(contract.vaxxx_pass(bob()), "Expected bob to be vaxxxed");
+        assert!(contract.vaxxx_pass(carol()), "Expected carol to be vaxxxed");
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires owner or medic role")]
+    fn vaxxx_batch_rejects_non_medic() {
+        let context = get_context(alice().to_string(), 0);
+        testing_env!(context);
+        let mut contract = Contract::new_default_meta(contract());
+
+        contract.vaxxx_batch(vec![bob()]);
+    }
+
 }
 
@@ -0,0 +1,72 @@
+use crate::*;
+
+pub type TokenId = u64;
+
+/// NEP-177 compliant contract-level metadata
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NFTContractMetadata {
+    pub spec: String,
+    pub name: String,
+    pub symbol: String,
+    pub icon: Option<String>,
+    pub base_uri: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+/// NEP-177 compliant token-level metadata
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<Base64VecU8>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub updated_at: Option<u64>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+}
+
+/// The on-chain representation of a single token.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Token {
+    //owner of the token
+    pub owner_id: AccountId,
+
+    //approved account IDs that have access to transfer the token, each with its own expiration
+    pub approved_account_ids: HashMap<AccountId, Approval>,
+
+    //the next approval ID to give out
+    pub next_approval_id: u64,
+
+    //keep track of the royalty percentages for the token in a hash map
+    pub royalty: HashMap<AccountId, u32>,
+}
+
+/// The JSON-friendly view of a token, returned by view calls.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonToken {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub metadata: TokenMetadata,
+    pub approved_account_ids: HashMap<AccountId, u64>,
+    pub royalty: HashMap<AccountId, u32>,
+}
+
+pub trait NonFungibleTokenMetadataProvider {
+    fn nft_metadata(&self) -> NFTContractMetadata;
+}
+
+#[near_bindgen]
+impl NonFungibleTokenMetadataProvider for Contract {
+    fn nft_metadata(&self) -> NFTContractMetadata {
+        self.metadata.get().unwrap()
+    }
+}
@@ -0,0 +1,109 @@
+use crate::*;
+use near_sdk::{env, require};
+
+#[near_bindgen]
+impl Contract {
+    #[payable]
+    pub fn nft_mint(
+        &mut self,
+        metadata: TokenMetadata,
+        receiver_id: AccountId,
+        perpetual_royalties: Option<HashMap<AccountId, u32>>,
+    ) {
+        self.assert_not_paused();
+
+        let initial_storage_usage = env::storage_usage();
+
+        let royalty = perpetual_royalties.unwrap_or_default();
+        require!(royalty.len() <= 7, "Cannot add more than 7 royalty amounts");
+
+        let token_id = self.next_token_id;
+
+        let token = Token {
+            owner_id: receiver_id,
+            approved_account_ids: Default::default(),
+            next_approval_id: 0,
+            royalty,
+        };
+
+        require!(
+            self.tokens_by_id.insert(&token_id, &token).is_none(),
+            "Token already exists"
+        );
+
+        self.token_metadata_by_id.insert(&token_id, &metadata);
+        self.internal_add_token_to_owner(&token.owner_id, &token_id);
+        self.next_token_id += 1;
+
+        NftMintLog {
+            owner_id: token.owner_id.to_string(),
+            token_ids: vec![token_id.to_string()],
+            memo: None,
+        }
+        .emit();
+
+        refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
+
+    /// Mint one token per `(receiver_id, metadata)` pair, allocating
+    /// sequential token ids, and emit a single `nft_mint` event covering all
+    /// of them. Restricted to the owner or a medic, like any other admin call
+    /// that seeds an outbreak. Any storage deposit over what was actually
+    /// used is refunded to the caller.
+    #[payable]
+    pub fn nft_batch_mint(&mut self, receivers: Vec<(AccountId, TokenMetadata)>) {
+        self.require_role();
+        self.assert_not_paused();
+        require!(!receivers.is_empty(), "Must mint at least one token");
+
+        let initial_storage_usage = env::storage_usage();
+
+        // Grouped by owner across the *whole* batch, not just consecutive
+        // entries, so e.g. [alice, bob, alice] still collapses to one log
+        // entry per owner. `owner_order` preserves each owner's first
+        // appearance so the emitted logs stay in a deterministic order.
+        let mut owner_order: Vec<AccountId> = Vec::new();
+        let mut tokens_by_owner: HashMap<AccountId, Vec<String>> = HashMap::new();
+
+        for (receiver_id, metadata) in receivers {
+            let token_id = self.next_token_id;
+
+            let token = Token {
+                owner_id: receiver_id.clone(),
+                approved_account_ids: Default::default(),
+                next_approval_id: 0,
+                royalty: Default::default(),
+            };
+
+            require!(
+                self.tokens_by_id.insert(&token_id, &token).is_none(),
+                "Token already exists"
+            );
+
+            self.token_metadata_by_id.insert(&token_id, &metadata);
+            self.internal_add_token_to_owner(&receiver_id, &token_id);
+            self.next_token_id += 1;
+
+            tokens_by_owner
+                .entry(receiver_id.clone())
+                .or_insert_with(|| {
+                    owner_order.push(receiver_id.clone());
+                    Vec::new()
+                })
+                .push(token_id.to_string());
+        }
+
+        let logs = owner_order
+            .into_iter()
+            .map(|owner_id| NftMintLog {
+                owner_id: owner_id.to_string(),
+                token_ids: tokens_by_owner.remove(&owner_id).unwrap(),
+                memo: None,
+            })
+            .collect();
+
+        NftMintLog::emit_many(logs);
+
+        refund_deposit(env::storage_usage() - initial_storage_usage);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+use near_sdk::{ext_contract, require, Gas, PromiseResult};
+
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(15_000_000_000_000);
+const GAS_FOR_NFT_ON_TRANSFER: Gas = Gas(25_000_000_000_000);
+const GAS_FOR_NFT_TRANSFER_CALL: Gas = Gas(
+    GAS_FOR_RESOLVE_TRANSFER.0 + GAS_FOR_NFT_ON_TRANSFER.0 + 5_000_000_000_000,
+);
+
+#[ext_contract(ext_nft_receiver)]
+pub trait NonFungibleTokenReceiver {
+    /// Invoked on the receiving contract after `nft_transfer_call`. Returning
+    /// `true` rejects the token (the resolver will revert ownership); `false`
+    /// keeps the transfer. A failed promise, or a result that isn't a bool,
+    /// is reverted just like a rejection.
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool>;
+}
+
+#[ext_contract(ext_self)]
+pub trait NonFungibleTokenResolver {
+    fn nft_resolve_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        mutant_receiver_id: AccountId,
+        token_id: TokenId,
+        mutant_token_id: TokenId,
+        approved_account_ids: HashMap<AccountId, Approval>,
+    ) -> bool;
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Move `token_id` to `receiver_id`. As a side effect of every transfer a
+    /// "mutant" strain of the token is minted and handed to `mutant_receiver_id`
+    /// (conventionally the original owner) -- thevarus doesn't leave just
+    /// because the original copy did.
+    pub fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        mutant_receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        self.assert_not_paused();
+
+        let sender_id = env::predecessor_account_id();
+        let previous_token =
+            self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo.clone());
+        self.internal_spawn_mutant(&previous_token.owner_id, &mutant_receiver_id, &token_id);
+
+        NftTransferLog {
+            authorized_id: if sender_id == previous_token.owner_id {
+                None
+            } else {
+                Some(sender_id.to_string())
+            },
+            old_owner_id: previous_token.owner_id.to_string(),
+            new_owner_id: receiver_id.to_string(),
+            token_ids: vec![token_id.to_string()],
+            memo,
+        }
+        .emit();
+    }
+
+    pub fn nft_token(&self, token_id: TokenId) -> Option<JsonToken> {
+        self.tokens_by_id.get(&token_id)?;
+        Some(self.json_token(token_id))
+    }
+
+    /// NEP-171 `nft_transfer_call`: transfer the token, then invoke
+    /// `nft_on_transfer` on `receiver_id`. If the receiver rejects the token
+    /// (returns `true`) or the cross-contract call fails outright, the
+    /// resolver reverts both the original token *and* the mutant spawned
+    /// alongside it, so a rejected transfer never leaves a stray infection
+    /// behind on the receiver's side.
+    pub fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        mutant_receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        self.assert_not_paused();
+        require!(
+            env::prepaid_gas() > GAS_FOR_NFT_TRANSFER_CALL,
+            "Not enough prepaid gas for nft_transfer_call"
+        );
+
+        let sender_id = env::predecessor_account_id();
+        let previous_token =
+            self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo.clone());
+        let mutant_token_id = self.internal_spawn_mutant(&previous_token.owner_id, &mutant_receiver_id, &token_id);
+
+        NftTransferLog {
+            authorized_id: if sender_id == previous_token.owner_id {
+                None
+            } else {
+                Some(sender_id.to_string())
+            },
+            old_owner_id: previous_token.owner_id.to_string(),
+            new_owner_id: receiver_id.to_string(),
+            token_ids: vec![token_id.to_string()],
+            memo,
+        }
+        .emit();
+
+        ext_nft_receiver::nft_on_transfer(
+            sender_id,
+            previous_token.owner_id.clone(),
+            token_id,
+            msg,
+            receiver_id.clone(),
+            NO_DEPOSIT,
+            env::prepaid_gas() - GAS_FOR_NFT_TRANSFER_CALL,
+        )
+        .then(ext_self::nft_resolve_transfer(
+            previous_token.owner_id,
+            receiver_id,
+            mutant_receiver_id,
+            token_id,
+            mutant_token_id,
+            previous_token.approved_account_ids,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
+        .into()
+    }
+
+    /// Resolves `nft_transfer_call`. Reverts the transferred token, and the
+    /// mutant minted alongside it, back to `owner_id` if the receiver
+    /// rejected the token or the call failed.
+    #[private]
+    pub fn nft_resolve_transfer(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        mutant_receiver_id: AccountId,
+        token_id: TokenId,
+        mutant_token_id: TokenId,
+        approved_account_ids: HashMap<AccountId, Approval>,
+    ) -> bool {
+        let must_revert = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<bool>(&value).unwrap_or(true)
+            }
+            PromiseResult::Failed => true,
+        };
+
+        if !must_revert {
+            return true;
+        }
+
+        if let Some(current_token) = self.tokens_by_id.get(&token_id) {
+            if current_token.owner_id == receiver_id {
+                self.internal_transfer(&receiver_id, &owner_id, &token_id, None, None);
+                if let Some(mut token) = self.tokens_by_id.get(&token_id) {
+                    token.approved_account_ids = approved_account_ids;
+                    self.tokens_by_id.insert(&token_id, &token);
+                }
+            }
+        }
+
+        if let Some(mutant_token) = self.tokens_by_id.get(&mutant_token_id) {
+            // The conventional case hands the mutant straight to `owner_id`, so
+            // there's nothing to reassign -- calling `internal_transfer` with
+            // sender == receiver would panic and unwind the revert above too.
+            if mutant_token.owner_id == mutant_receiver_id && mutant_receiver_id != owner_id {
+                self.internal_transfer(&mutant_receiver_id, &owner_id, &mutant_token_id, None, None);
+            }
+        }
+
+        false
+    }
+}
@@ -0,0 +1,81 @@
+use crate::*;
+use near_sdk::{require, Balance};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Payout {
+    pub payout: HashMap<AccountId, U128>,
+}
+
+pub trait NonFungibleTokenRoyalty {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout;
+
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: u32,
+    ) -> Payout;
+}
+
+#[near_bindgen]
+impl NonFungibleTokenRoyalty for Contract {
+    fn nft_payout(&self, token_id: TokenId, balance: U128, max_len_payout: u32) -> Payout {
+        let token = self.tokens_by_id.get(&token_id).expect("No token found");
+        require!(
+            (token.royalty.len() as u32) <= max_len_payout,
+            "Market cannot payout that many royalties"
+        );
+
+        let balance_u128 = u128::from(balance);
+        let mut payout: HashMap<AccountId, U128> = HashMap::new();
+        for (account, percentage) in token.royalty.iter() {
+            payout.insert(
+                account.clone(),
+                U128(royalty_to_payout(*percentage, balance_u128)),
+            );
+        }
+
+        Payout { payout }
+    }
+
+    #[payable]
+    fn nft_transfer_payout(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        balance: U128,
+        max_len_payout: u32,
+    ) -> Payout {
+        self.assert_not_paused();
+
+        let sender_id = env::predecessor_account_id();
+        let token = self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+
+        require!(
+            (token.royalty.len() as u32) <= max_len_payout,
+            "Market cannot payout that many royalties"
+        );
+
+        let balance_u128 = u128::from(balance);
+        let mut payout: HashMap<AccountId, U128> = HashMap::new();
+        for (account, percentage) in token.royalty.iter() {
+            payout.insert(
+                account.clone(),
+                U128(royalty_to_payout(*percentage, balance_u128)),
+            );
+        }
+
+        Payout { payout }
+    }
+}
+
+pub(crate) fn royalty_to_payout(royalty_percentage: u32, amount_to_pay: Balance) -> u128 {
+    royalty_percentage as u128 * amount_to_pay / 10_000u128
+}
@@ -0,0 +1,90 @@
+//! near-workspaces integration coverage for `upgrade()`/`migrate()`: deploys
+//! the contract, mints a token and vaxxxes an account, then drives the real
+//! `upgrade` receipt chain (`Promise::deploy_contract` + chained
+//! `function_call` to `migrate`) and asserts both survive.
+//!
+//! There's no separate v2 crate in this tree, so "new WASM" here is the same
+//! contract redeployed over itself -- the point is exercising the actual
+//! upgrade receipt chain, not a layout change. `upgrade.rs`'s unit test
+//! covers `migrate`'s Borsh round-trip in isolation; this covers the
+//! cross-contract plumbing around it.
+
+use near_workspaces::types::NearToken;
+use serde_json::json;
+
+const WASM_FILEPATH: &str = "../target/wasm32-unknown-unknown/release/nft_contract.wasm";
+
+async fn deploy(
+    worker: &near_workspaces::Worker<near_workspaces::network::Sandbox>,
+) -> anyhow::Result<near_workspaces::Contract> {
+    let wasm = std::fs::read(WASM_FILEPATH)?;
+    let contract = worker.dev_deploy(&wasm).await?;
+
+    contract
+        .call("new_default_meta")
+        .args_json(json!({ "owner_id": contract.id() }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    Ok(contract)
+}
+
+#[tokio::test]
+async fn upgrade_preserves_tokens_and_vaxxxed() -> anyhow::Result<()> {
+    let worker = near_workspaces::sandbox().await?;
+    let contract = deploy(&worker).await?;
+
+    contract
+        .call("nft_mint")
+        .args_json(json!({
+            "metadata": {
+                "title": "thevarus",
+                "description": "pathogen",
+            },
+            "receiver_id": contract.id(),
+            "perpetual_royalties": null,
+        }))
+        .deposit(NearToken::from_millinear(50))
+        .transact()
+        .await?
+        .into_result()?;
+
+    contract
+        .call("vaxxx")
+        .args_json(json!({ "account_id": contract.id() }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let wasm = std::fs::read(WASM_FILEPATH)?;
+    contract
+        .call("upgrade")
+        .args(wasm)
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+
+    let token: serde_json::Value = contract
+        .call("nft_token")
+        .args_json(json!({ "token_id": 0 }))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(
+        token["owner_id"],
+        contract.id().to_string(),
+        "The minted token should still belong to the contract account after upgrade."
+    );
+
+    let vaxxxed: bool = contract
+        .call("vaxxx_pass")
+        .args_json(json!({ "account_id": contract.id() }))
+        .view()
+        .await?
+        .json()?;
+    assert!(vaxxxed, "The vaxxxed set should survive the upgrade.");
+
+    Ok(())
+}